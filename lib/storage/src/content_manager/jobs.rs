@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::content_manager::errors::StorageError;
+
+/// Upper bound on how many snapshot operations (create/recover/full-snapshot)
+/// are allowed to run concurrently in the background.
+const SNAPSHOT_JOB_CONCURRENCY: usize = 4;
+
+/// How long a finished job's status is kept around before it is evicted.
+const SNAPSHOT_JOB_TTL: Duration = Duration::from_secs(3600);
+
+/// How often the background sweeper checks for terminal jobs past
+/// `SNAPSHOT_JOB_TTL`, independent of whether any job ever completes again.
+const SNAPSHOT_JOB_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct SnapshotJobId(pub Uuid);
+
+/// Status of a background snapshot operation, as reported by the
+/// `GET .../snapshots/jobs/{job_id}` endpoints.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(tag = "status")]
+pub enum SnapshotJobStatus {
+    Pending,
+    Running,
+    Succeeded { snapshot_name: String },
+    Failed { error: String },
+}
+
+struct SnapshotJobEntry {
+    status: SnapshotJobStatus,
+    finished_at: Option<Instant>,
+}
+
+/// Background worker pool for snapshot create/recover operations, modeled on
+/// pict-rs's `backgrounded` + `queue` pair: enqueuing a job hands back an id
+/// right away, a bounded number of jobs run at a time, and terminal statuses
+/// are kept around for `SNAPSHOT_JOB_TTL` so callers can poll for the
+/// result. Lives in `storage::content_manager` rather than the actix layer
+/// so non-HTTP callers (CLI, other background tasks) can share it too.
+#[derive(Clone)]
+pub struct SnapshotJobManager {
+    semaphore: Arc<Semaphore>,
+    jobs: Arc<Mutex<HashMap<Uuid, SnapshotJobEntry>>>,
+}
+
+impl Default for SnapshotJobManager {
+    fn default() -> Self {
+        Self::new(SNAPSHOT_JOB_CONCURRENCY)
+    }
+}
+
+impl SnapshotJobManager {
+    pub fn new(max_concurrent_jobs: usize) -> Self {
+        let manager = Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent_jobs)),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+        };
+        manager.spawn_ttl_sweeper();
+        manager
+    }
+
+    /// Periodically evicts terminal jobs past `SNAPSHOT_JOB_TTL` on its own
+    /// schedule, so entries aren't stuck relying on some other job finishing
+    /// to trigger cleanup.
+    fn spawn_ttl_sweeper(&self) {
+        let jobs = self.jobs.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SNAPSHOT_JOB_SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                jobs.lock()
+                    .unwrap()
+                    .retain(|_, entry| match entry.finished_at {
+                        Some(finished_at) => finished_at.elapsed() < SNAPSHOT_JOB_TTL,
+                        None => true,
+                    });
+            }
+        });
+    }
+
+    /// Registers `task` to run as soon as a worker permit is free and
+    /// returns its id immediately.
+    pub fn enqueue<F>(&self, task: F) -> SnapshotJobId
+    where
+        F: std::future::Future<Output = std::result::Result<String, StorageError>> + Send + 'static,
+    {
+        let job_id = Uuid::new_v4();
+        self.jobs.lock().unwrap().insert(
+            job_id,
+            SnapshotJobEntry {
+                status: SnapshotJobStatus::Pending,
+                finished_at: None,
+            },
+        );
+
+        let semaphore = self.semaphore.clone();
+        let jobs = self.jobs.clone();
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire().await;
+            if let Some(entry) = jobs.lock().unwrap().get_mut(&job_id) {
+                entry.status = SnapshotJobStatus::Running;
+            }
+
+            let status = match task.await {
+                Ok(snapshot_name) => SnapshotJobStatus::Succeeded { snapshot_name },
+                Err(err) => SnapshotJobStatus::Failed {
+                    error: err.to_string(),
+                },
+            };
+
+            let mut jobs = jobs.lock().unwrap();
+            if let Some(entry) = jobs.get_mut(&job_id) {
+                entry.status = status;
+                entry.finished_at = Some(Instant::now());
+            }
+            jobs.retain(|_, entry| match entry.finished_at {
+                Some(finished_at) => finished_at.elapsed() < SNAPSHOT_JOB_TTL,
+                None => true,
+            });
+        });
+
+        SnapshotJobId(job_id)
+    }
+
+    pub fn status(&self, job_id: Uuid) -> Option<SnapshotJobStatus> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .get(&job_id)
+            .map(|entry| entry.status.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn wait_for_terminal_status(
+        manager: &SnapshotJobManager,
+        job_id: Uuid,
+    ) -> Option<SnapshotJobStatus> {
+        for _ in 0..200 {
+            match manager.status(job_id) {
+                Some(SnapshotJobStatus::Pending) | Some(SnapshotJobStatus::Running) => {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                other => return other,
+            }
+        }
+        manager.status(job_id)
+    }
+
+    #[tokio::test]
+    async fn enqueued_job_transitions_to_succeeded() {
+        let manager = SnapshotJobManager::new(1);
+        let job_id = manager.enqueue(async { Ok("test-snapshot".to_string()) });
+
+        match wait_for_terminal_status(&manager, job_id.0).await {
+            Some(SnapshotJobStatus::Succeeded { snapshot_name }) => {
+                assert_eq!(snapshot_name, "test-snapshot");
+            }
+            other => panic!("expected Succeeded, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn enqueued_job_transitions_to_failed() {
+        let manager = SnapshotJobManager::new(1);
+        let job_id =
+            manager.enqueue(async { Err(StorageError::service_error("boom".to_string())) });
+
+        match wait_for_terminal_status(&manager, job_id.0).await {
+            Some(SnapshotJobStatus::Failed { error }) => assert_eq!(error, "boom"),
+            other => panic!("expected Failed, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_job_has_no_status() {
+        let manager = SnapshotJobManager::new(1);
+        assert!(manager.status(Uuid::new_v4()).is_none());
+    }
+}
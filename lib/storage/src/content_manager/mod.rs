@@ -0,0 +1,5 @@
+pub mod collection_meta_ops;
+pub mod errors;
+pub mod jobs;
+pub mod snapshots;
+pub mod toc;
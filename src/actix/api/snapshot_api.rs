@@ -1,16 +1,29 @@
-use std::path::Path as StdPath;
+use std::future::{ready, Ready};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use actix_files::NamedFile;
-use actix_multipart::form::tempfile::TempFile;
-use actix_multipart::form::MultipartForm;
+use actix_multipart::Multipart;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
 use actix_web::rt::time::Instant;
-use actix_web::{delete, get, post, put, web, Responder, Result};
+use actix_web::{
+    delete, forward_ready, get, post, put, web, HttpRequest, HttpResponse, Responder,
+    ResponseError, Result,
+};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::snapshot_ops::{SnapshotPriority, SnapshotRecover};
+use async_trait::async_trait;
+use collection::operations::snapshot_ops::{
+    SnapshotDescription, SnapshotPriority, SnapshotRecover,
+};
+use futures_util::future::LocalBoxFuture;
+use futures_util::{StreamExt, TryStreamExt};
 use reqwest::Url;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::jobs::{SnapshotJobId, SnapshotJobManager};
 use storage::content_manager::snapshots::recover::do_recover_from_snapshot;
 use storage::content_manager::snapshots::{
     do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
@@ -18,6 +31,8 @@ use storage::content_manager::snapshots::{
 };
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
+use tempfile::NamedTempFile;
+use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 use validator::Validate;
 
@@ -34,6 +49,11 @@ struct SnapshotPath {
     name: String,
 }
 
+#[derive(Serialize, JsonSchema)]
+struct SnapshotJobAccepted {
+    job_id: SnapshotJobId,
+}
+
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct SnapshotUploadingParam {
     pub wait: Option<bool>,
@@ -45,88 +65,892 @@ pub struct SnapshottingParam {
     pub wait: Option<bool>,
 }
 
-#[derive(MultipartForm)]
-pub struct SnapshottingForm {
-    snapshot: TempFile,
-}
-
 // Actix specific code
 pub async fn do_get_full_snapshot(
     dispatcher: &Dispatcher,
+    req: HttpRequest,
     snapshot_name: &str,
     wait: bool,
-) -> Result<NamedFile> {
-    let dispatcher = dispatcher.clone();
-    let snapshot_name = snapshot_name.to_string();
-    let task =
-        tokio::spawn(async move { get_full_snapshot_path(dispatcher.toc(), &snapshot_name).await });
+) -> Result<HttpResponse> {
+    let spawned_dispatcher = dispatcher.clone();
+    let spawned_snapshot_name = snapshot_name.to_string();
+    let task = tokio::spawn(async move {
+        get_full_snapshot_path(spawned_dispatcher.toc(), &spawned_snapshot_name).await
+    });
 
     if wait {
-        let filename = task.await;
-        if let Err(e) = filename {
+        let local_path = task.await;
+        if let Err(e) = local_path {
             return Err(ErrorInternalServerError(e.to_string()));
         }
-        let filename = filename.unwrap().map_err(storage_into_actix_error)?;
-        Ok(NamedFile::open(filename)?)
+        let local_path = local_path.unwrap().map_err(storage_into_actix_error)?;
+        serve_snapshot_file(&req, dispatcher.toc(), None, &local_path).await
     } else {
-        Ok(NamedFile::open("not_found")?)
+        Ok(NamedFile::open("not_found")?.into_response(&req))
+    }
+}
+
+/// A partial or whole object fetched from [`SnapshotStore::get_stream`],
+/// together with enough metadata to answer a `Range` request: the
+/// (inclusive) byte range actually returned and the object's total size.
+pub struct SnapshotObjectStream {
+    pub total_len: u64,
+    pub range: (u64, u64),
+    pub body: std::pin::Pin<
+        Box<dyn futures_util::Stream<Item = std::result::Result<web::Bytes, StorageError>> + Send>,
+    >,
+}
+
+/// Where snapshot bytes actually live, abstracted away from the handlers the
+/// same way pict-rs decouples its upload handling from a concrete `Store`.
+/// `collection_name` is `None` for full snapshots, which are kept at the
+/// store root rather than under a collection prefix.
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    async fn put(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> std::result::Result<Url, StorageError>;
+
+    async fn get(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+    ) -> std::result::Result<Vec<u8>, StorageError>;
+
+    /// Like [`SnapshotStore::get`], but streams the (optionally
+    /// range-restricted) object instead of buffering it whole, so large
+    /// objects don't have to fit in memory to be served. `range` is an
+    /// inclusive `(start, end)` byte range; `end: None` means "to the end of
+    /// the object". The default reads the whole object via `get` and slices
+    /// it in memory - stores backed by an HTTP GET (e.g. S3) should override
+    /// this to proxy the range to the underlying request instead.
+    async fn get_stream(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        range: Option<(u64, Option<u64>)>,
+    ) -> std::result::Result<SnapshotObjectStream, StorageError> {
+        let data = self.get(collection_name, filename).await?;
+        let total_len = data.len() as u64;
+        let last_byte = total_len.saturating_sub(1);
+        let (start, end) = match range {
+            Some((start, Some(end))) => (start, end.min(last_byte)),
+            Some((start, None)) => (start, last_byte),
+            None => (0, last_byte),
+        };
+        let slice = data
+            .get(start as usize..=(end as usize).min(data.len().saturating_sub(1)))
+            .unwrap_or_default()
+            .to_vec();
+        Ok(SnapshotObjectStream {
+            total_len,
+            range: (start, end),
+            body: futures_util::stream::once(async move { Ok(web::Bytes::from(slice)) }).boxed(),
+        })
+    }
+
+    async fn delete(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+    ) -> std::result::Result<(), StorageError>;
+
+    async fn list(
+        &self,
+        collection_name: Option<&str>,
+    ) -> std::result::Result<Vec<String>, StorageError>;
+
+    /// Whether this store already *is* the local snapshots directory. Lets
+    /// callers skip syncing a freshly-written local snapshot into "the
+    /// store" when the store and local disk are the same thing, and lets
+    /// downloads keep using [`NamedFile`]'s Range support on that path.
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    /// Persists a temp file that already holds the full upload, reading it
+    /// into memory and delegating to [`SnapshotStore::put`] by default.
+    /// Stores that can adopt the file in place (the local filesystem, via a
+    /// rename) should override this to avoid a second full copy.
+    async fn put_file(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        file: NamedTempFile,
+    ) -> std::result::Result<Url, StorageError> {
+        let data = std::fs::read(file.path())?;
+        self.put(collection_name, filename, data).await
     }
 }
 
-pub fn do_save_uploaded_snapshot(
+pub struct LocalFileStore {
+    base_path: PathBuf,
+}
+
+impl LocalFileStore {
+    pub fn new(base_path: impl Into<PathBuf>) -> Self {
+        Self {
+            base_path: base_path.into(),
+        }
+    }
+
+    fn object_path(&self, collection_name: Option<&str>, filename: &str) -> PathBuf {
+        match collection_name {
+            Some(collection_name) => self.base_path.join(collection_name).join(filename),
+            None => self.base_path.join(filename),
+        }
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for LocalFileStore {
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    async fn put(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> std::result::Result<Url, StorageError> {
+        let path = self.object_path(collection_name, filename);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, data)?;
+
+        let absolute_path = path.canonicalize()?;
+        Url::from_file_path(&absolute_path).map_err(|_| {
+            StorageError::service_error(format!(
+                "Failed to convert path to URL: {}",
+                absolute_path.display()
+            ))
+        })
+    }
+
+    async fn get(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+    ) -> std::result::Result<Vec<u8>, StorageError> {
+        Ok(std::fs::read(self.object_path(collection_name, filename))?)
+    }
+
+    async fn delete(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+    ) -> std::result::Result<(), StorageError> {
+        Ok(std::fs::remove_file(
+            self.object_path(collection_name, filename),
+        )?)
+    }
+
+    async fn list(
+        &self,
+        collection_name: Option<&str>,
+    ) -> std::result::Result<Vec<String>, StorageError> {
+        let dir = match collection_name {
+            Some(collection_name) => self.base_path.join(collection_name),
+            None => self.base_path.clone(),
+        };
+
+        // A collection (or the store root) that has never had a snapshot
+        // simply has no directory yet - that's an empty list, not an error.
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            // Collection snapshots live in subdirectories, which are not
+            // objects of this level.
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    async fn put_file(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        file: NamedTempFile,
+    ) -> std::result::Result<Url, StorageError> {
+        let path = self.object_path(collection_name, filename);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        file.persist(&path)
+            .map_err(|err| StorageError::service_error(err.to_string()))?;
+
+        let absolute_path = path.canonicalize()?;
+        Url::from_file_path(&absolute_path).map_err(|_| {
+            StorageError::service_error(format!(
+                "Failed to convert path to URL: {}",
+                absolute_path.display()
+            ))
+        })
+    }
+}
+
+/// S3-compatible store for snapshot files. Downloads are streamed from the
+/// bucket; uploads are staged in a local temp file and newly created
+/// snapshots are written locally before being copied into the bucket.
+/// Object keys mirror the local layout: `filename` for
+/// full snapshots, `collection_name/filename` for collection snapshots.
+pub struct S3Store {
+    bucket: rusty_s3::Bucket,
+    credentials: rusty_s3::Credentials,
+    client: reqwest::Client,
+}
+
+impl S3Store {
+    pub fn new(
+        endpoint: Url,
+        bucket_name: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    ) -> std::result::Result<Self, StorageError> {
+        let bucket = rusty_s3::Bucket::new(endpoint, rusty_s3::UrlStyle::Path, bucket_name, region)
+            .map_err(|err| StorageError::service_error(format!("invalid S3 endpoint: {err}")))?;
+        Ok(Self {
+            bucket,
+            credentials: rusty_s3::Credentials::new(access_key, secret_key),
+            client: reqwest::Client::new(),
+        })
+    }
+
+    fn object_key(collection_name: Option<&str>, filename: &str) -> String {
+        match collection_name {
+            Some(collection_name) => format!("{collection_name}/{filename}"),
+            None => filename.to_string(),
+        }
+    }
+}
+
+const S3_SIGNED_URL_TTL: Duration = Duration::from_secs(60);
+
+/// Parses a `Content-Range: bytes start-end/total` response header, as
+/// returned by S3 for a ranged `GET`, into `(start, end, total)`.
+fn parse_content_range(value: &str) -> Option<(u64, u64, u64)> {
+    let spec = value.strip_prefix("bytes ")?;
+    let (range, total) = spec.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    Some((start.parse().ok()?, end.parse().ok()?, total.parse().ok()?))
+}
+
+#[async_trait]
+impl SnapshotStore for S3Store {
+    async fn put(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> std::result::Result<Url, StorageError> {
+        let key = Self::object_key(collection_name, filename);
+        let action = self.bucket.put_object(Some(&self.credentials), &key);
+        let url = action.sign(S3_SIGNED_URL_TTL);
+
+        self.client
+            .put(url)
+            .body(data)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| StorageError::service_error(format!("S3 upload failed: {err}")))?;
+
+        self.bucket
+            .object_url(&key)
+            .map_err(|err| StorageError::service_error(format!("S3 upload failed: {err}")))
+    }
+
+    async fn get(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+    ) -> std::result::Result<Vec<u8>, StorageError> {
+        let key = Self::object_key(collection_name, filename);
+        let action = self.bucket.get_object(Some(&self.credentials), &key);
+        let url = action.sign(S3_SIGNED_URL_TTL);
+
+        let bytes = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| StorageError::service_error(format!("S3 download failed: {err}")))?
+            .bytes()
+            .await
+            .map_err(|err| StorageError::service_error(format!("S3 download failed: {err}")))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    async fn get_stream(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        range: Option<(u64, Option<u64>)>,
+    ) -> std::result::Result<SnapshotObjectStream, StorageError> {
+        let key = Self::object_key(collection_name, filename);
+        let action = self.bucket.get_object(Some(&self.credentials), &key);
+        let url = action.sign(S3_SIGNED_URL_TTL);
+
+        let mut request = self.client.get(url);
+        if let Some((start, end)) = range {
+            let value = match end {
+                Some(end) => format!("bytes={start}-{end}"),
+                None => format!("bytes={start}-"),
+            };
+            request = request.header(reqwest::header::RANGE, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| StorageError::service_error(format!("S3 download failed: {err}")))?;
+
+        let (start, end, total_len) = match response.headers().get(reqwest::header::CONTENT_RANGE) {
+            Some(value) => value
+                .to_str()
+                .ok()
+                .and_then(parse_content_range)
+                .ok_or_else(|| {
+                    StorageError::service_error(
+                        "S3 returned an unparseable Content-Range".to_string(),
+                    )
+                })?,
+            None => {
+                let total_len = response.content_length().unwrap_or(0);
+                (0, total_len.saturating_sub(1), total_len)
+            }
+        };
+
+        let body = response
+            .bytes_stream()
+            .map_err(|err| StorageError::service_error(format!("S3 download failed: {err}")))
+            .boxed();
+
+        Ok(SnapshotObjectStream {
+            total_len,
+            range: (start, end),
+            body,
+        })
+    }
+
+    async fn put_file(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+        file: NamedTempFile,
+    ) -> std::result::Result<Url, StorageError> {
+        let key = Self::object_key(collection_name, filename);
+        let action = self.bucket.put_object(Some(&self.credentials), &key);
+        let url = action.sign(S3_SIGNED_URL_TTL);
+
+        let size = file.as_file().metadata()?.len();
+        let async_file = tokio::fs::File::from_std(
+            file.reopen()
+                .map_err(|err| StorageError::service_error(err.to_string()))?,
+        );
+        let body = reqwest::Body::wrap_stream(ReaderStream::new(async_file));
+
+        self.client
+            .put(url)
+            .header(reqwest::header::CONTENT_LENGTH, size)
+            .body(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| StorageError::service_error(format!("S3 upload failed: {err}")))?;
+
+        self.bucket
+            .object_url(&key)
+            .map_err(|err| StorageError::service_error(format!("S3 upload failed: {err}")))
+    }
+
+    async fn delete(
+        &self,
+        collection_name: Option<&str>,
+        filename: &str,
+    ) -> std::result::Result<(), StorageError> {
+        let key = Self::object_key(collection_name, filename);
+        let action = self.bucket.delete_object(Some(&self.credentials), &key);
+        let url = action.sign(S3_SIGNED_URL_TTL);
+
+        self.client
+            .delete(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| StorageError::service_error(format!("S3 delete failed: {err}")))?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        collection_name: Option<&str>,
+    ) -> std::result::Result<Vec<String>, StorageError> {
+        let prefix = collection_name.map(|name| format!("{name}/"));
+        let mut action = self.bucket.list_objects_v2(Some(&self.credentials));
+        if let Some(prefix) = &prefix {
+            action.with_prefix(prefix);
+        }
+        let url = action.sign(S3_SIGNED_URL_TTL);
+
+        let body = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| StorageError::service_error(format!("S3 list failed: {err}")))?
+            .text()
+            .await
+            .map_err(|err| StorageError::service_error(format!("S3 list failed: {err}")))?;
+
+        let parsed = rusty_s3::actions::ListObjectsV2::parse_response(&body)
+            .map_err(|err| StorageError::service_error(format!("S3 list failed: {err}")))?;
+
+        // Report bare file names, like the local store: strip the collection
+        // prefix, and at the root keep only keys outside any collection.
+        Ok(parsed
+            .contents
+            .into_iter()
+            .filter_map(|object| match &prefix {
+                Some(prefix) => object.key.strip_prefix(prefix.as_str()).map(str::to_string),
+                None => (!object.key.contains('/')).then_some(object.key),
+            })
+            .collect())
+    }
+}
+
+/// Picks the snapshot store from environment configuration: an S3 bucket
+/// when `QDRANT__STORAGE__SNAPSHOTS_S3_BUCKET` is set, the local snapshots
+/// directory otherwise. This mirrors how the rest of qdrant's storage
+/// settings can be overridden via `QDRANT__...` environment variables.
+fn snapshot_store(toc: &TableOfContent) -> Arc<dyn SnapshotStore> {
+    match std::env::var("QDRANT__STORAGE__SNAPSHOTS_S3_BUCKET") {
+        Ok(bucket_name) => {
+            let endpoint = std::env::var("QDRANT__STORAGE__SNAPSHOTS_S3_ENDPOINT")
+                .unwrap_or_else(|_| "https://s3.amazonaws.com".to_string());
+            let region = std::env::var("QDRANT__STORAGE__SNAPSHOTS_S3_REGION")
+                .unwrap_or_else(|_| "us-east-1".to_string());
+            let access_key =
+                std::env::var("QDRANT__STORAGE__SNAPSHOTS_S3_ACCESS_KEY").unwrap_or_default();
+            let secret_key =
+                std::env::var("QDRANT__STORAGE__SNAPSHOTS_S3_SECRET_KEY").unwrap_or_default();
+            let endpoint: Url = endpoint
+                .parse()
+                .expect("QDRANT__STORAGE__SNAPSHOTS_S3_ENDPOINT must be a valid URL");
+
+            Arc::new(
+                S3Store::new(endpoint, bucket_name, region, access_key, secret_key)
+                    .expect("invalid S3 snapshot store configuration"),
+            )
+        }
+        Err(_) => Arc::new(LocalFileStore::new(toc.snapshots_path())),
+    }
+}
+
+/// Copies a snapshot that was just written to local disk by `do_create_*`
+/// into the configured store, unless the configured store already *is*
+/// local disk. `local_path` is the file `do_create_*` reported.
+async fn sync_created_snapshot(
+    toc: &TableOfContent,
+    collection_name: Option<&str>,
+    local_path: &std::path::Path,
+) -> std::result::Result<(), StorageError> {
+    let store = snapshot_store(toc);
+    if store.is_local() {
+        return Ok(());
+    }
+
+    let filename = local_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            StorageError::service_error(format!(
+                "snapshot path has no file name: {}",
+                local_path.display()
+            ))
+        })?;
+    let data = std::fs::read(local_path)?;
+    store.put(collection_name, filename, data).await?;
+    Ok(())
+}
+
+/// Parses a single `Range: bytes=start-end` request header into an
+/// inclusive `(start, end)` range, the same minimal syntax [`NamedFile`]'s
+/// own Range handling accepts. Multi-range requests (`bytes=0-10,20-30`)
+/// and any other unit fall back to `None`, i.e. serve the whole object.
+fn parse_byte_range(req: &HttpRequest) -> Option<(u64, Option<u64>)> {
+    let header = req
+        .headers()
+        .get(actix_web::http::header::RANGE)?
+        .to_str()
+        .ok()?;
+    let spec = header.strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let start = start.parse().ok()?;
+    let end = if end.is_empty() {
+        None
+    } else {
+        Some(end.parse().ok()?)
+    };
+    Some((start, end))
+}
+
+/// Serves a snapshot file from the configured store: for a local store this
+/// keeps using [`NamedFile`] so Range/If-Range requests from chunk0-2 keep
+/// working, for a remote store (e.g. S3) it proxies the same Range request
+/// to the store and streams the (possibly partial) object back without
+/// buffering it whole.
+async fn serve_snapshot_file(
+    req: &HttpRequest,
+    toc: &TableOfContent,
+    collection_name: Option<&str>,
+    local_path: &std::path::Path,
+) -> Result<HttpResponse> {
+    let store = snapshot_store(toc);
+    if store.is_local() {
+        return Ok(NamedFile::open(local_path)?.into_response(req));
+    }
+
+    let filename = local_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            ErrorInternalServerError(format!(
+                "snapshot path has no file name: {}",
+                local_path.display()
+            ))
+        })?;
+
+    let range = parse_byte_range(req);
+    let object = store
+        .get_stream(collection_name, filename, range)
+        .await
+        .map_err(storage_into_actix_error)?;
+    let is_partial = range.is_some() && object.range != (0, object.total_len.saturating_sub(1));
+    let content_length = object.range.1.saturating_sub(object.range.0) + 1;
+    let body = object.body.map_err(ErrorInternalServerError);
+
+    let mut response = if is_partial {
+        HttpResponse::PartialContent()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .content_type("application/octet-stream")
+        .insert_header((actix_web::http::header::ACCEPT_RANGES, "bytes"))
+        .insert_header((
+            actix_web::http::header::CONTENT_LENGTH,
+            content_length.to_string(),
+        ));
+    if is_partial {
+        response.insert_header((
+            actix_web::http::header::CONTENT_RANGE,
+            format!(
+                "bytes {}-{}/{}",
+                object.range.0, object.range.1, object.total_len
+            ),
+        ));
+    }
+    Ok(response.streaming(body))
+}
+
+/// Minimal listing of what the configured store reports for a collection
+/// (or, for `None`, full snapshots at the store root). Used only when the
+/// store isn't local disk, where the richer `do_list_*` metadata (creation
+/// time, size) isn't available without downloading every object.
+#[derive(Serialize, JsonSchema)]
+struct StoredSnapshotDescription {
+    name: String,
+}
+
+async fn list_store_snapshots(
+    store: &dyn SnapshotStore,
+    collection_name: Option<&str>,
+) -> std::result::Result<Vec<StoredSnapshotDescription>, StorageError> {
+    Ok(store
+        .list(collection_name)
+        .await?
+        .into_iter()
+        .map(|name| StoredSnapshotDescription { name })
+        .collect())
+}
+
+/// One-shot migration that copies every object from `source` to
+/// `destination`, collection by collection, so operators can move snapshot
+/// storage (e.g. local disk to S3) online without losing existing snapshots.
+pub async fn migrate_store(
+    source: &dyn SnapshotStore,
+    destination: &dyn SnapshotStore,
+    collection_names: &[String],
+) -> std::result::Result<(), StorageError> {
+    for filename in source.list(None).await? {
+        let data = source.get(None, &filename).await?;
+        destination.put(None, &filename, data).await?;
+    }
+
+    for collection_name in collection_names {
+        for filename in source.list(Some(collection_name)).await? {
+            let data = source.get(Some(collection_name), &filename).await?;
+            destination
+                .put(Some(collection_name), &filename, data)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Admin endpoint wiring up [`migrate_store`]: copies every snapshot from the
+/// local snapshots directory into whichever store is currently configured
+/// (e.g. S3), so operators can switch storage backends without losing
+/// existing snapshots. The local copies are left in place. The collections to migrate are read from the TOC
+/// itself rather than the caller, so nothing can be forgotten.
+#[post("/snapshots/migrate")]
+async fn migrate_snapshots(dispatcher: web::Data<Dispatcher>) -> impl Responder {
+    let timing = Instant::now();
+    let toc = dispatcher.get_ref().toc();
+    let source = LocalFileStore::new(toc.snapshots_path());
+    let destination = snapshot_store(toc);
+
+    let collection_names: Vec<String> = do_list_collections(toc)
+        .await
+        .collections
+        .into_iter()
+        .map(|collection| collection.name)
+        .collect();
+
+    let response = migrate_store(&source, destination.as_ref(), &collection_names)
+        .await
+        .map(|()| true);
+    process_response(response, timing)
+}
+
+pub async fn do_save_uploaded_snapshot(
     toc: &TableOfContent,
     collection_name: &str,
-    snapshot: TempFile,
+    snapshot_file: NamedTempFile,
+    file_name: Option<String>,
 ) -> std::result::Result<Url, StorageError> {
-    let filename = snapshot.file_name.unwrap_or(Uuid::new_v4().to_string());
-    let path = StdPath::new(toc.snapshots_path())
-        .join(collection_name)
-        .join(filename);
+    let filename = file_name.unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    snapshot_store(toc)
+        .put_file(Some(collection_name), &filename, snapshot_file)
+        .await
+}
+
+/// Default ceiling on how large a single snapshot upload may be, overridable
+/// via `QDRANT__STORAGE__SNAPSHOTS_MAX_UPLOAD_SIZE_BYTES`.
+const DEFAULT_MAX_SNAPSHOT_UPLOAD_SIZE: u64 = 32 * 1024 * 1024 * 1024;
+
+/// Default ceiling on how long a snapshot upload may take to process,
+/// overridable via `QDRANT__STORAGE__SNAPSHOTS_UPLOAD_DEADLINE_SECONDS`.
+const DEFAULT_SNAPSHOT_UPLOAD_DEADLINE: Duration = Duration::from_secs(30 * 60);
+
+fn max_snapshot_upload_size() -> u64 {
+    std::env::var("QDRANT__STORAGE__SNAPSHOTS_MAX_UPLOAD_SIZE_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_SNAPSHOT_UPLOAD_SIZE)
+}
+
+fn snapshot_upload_deadline() -> Duration {
+    std::env::var("QDRANT__STORAGE__SNAPSHOTS_UPLOAD_DEADLINE_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_SNAPSHOT_UPLOAD_DEADLINE)
+}
+
+/// A `snapshot` multipart field that has been streamed to disk.
+struct StreamedSnapshotUpload {
+    file: NamedTempFile,
+    file_name: Option<String>,
+}
+
+/// Writes a byte-chunk stream to `file`, aborting with `413 Payload Too
+/// Large` once more than `max_size` bytes have been written in total.
+/// Extracted from `receive_uploaded_snapshot` so the size-limit/abort logic
+/// can be unit-tested without real multipart/HTTP infrastructure.
+async fn write_limited_stream<S>(
+    file: &mut NamedTempFile,
+    mut chunks: S,
+    max_size: u64,
+) -> std::result::Result<(), actix_web::Error>
+where
+    S: futures_util::Stream<Item = std::result::Result<web::Bytes, actix_web::Error>> + Unpin,
+{
+    let mut written = 0u64;
+    while let Some(chunk) = chunks.try_next().await? {
+        written += chunk.len() as u64;
+        if written > max_size {
+            return Err(actix_web::error::ErrorPayloadTooLarge(format!(
+                "snapshot upload exceeds the {max_size} byte limit"
+            )));
+        }
+        file.write_all(&chunk)
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+    }
+    Ok(())
+}
+
+/// Streams the `snapshot` multipart field straight to a temp file, aborting
+/// with `413 Payload Too Large` once more than `max_size` bytes arrive. The
+/// temp file is deleted automatically (via `NamedTempFile`'s `Drop`) if the
+/// upload is aborted or fails partway through.
+async fn receive_uploaded_snapshot(
+    mut multipart: Multipart,
+    max_size: u64,
+) -> std::result::Result<StreamedSnapshotUpload, actix_web::Error> {
+    while let Some(field) = multipart
+        .try_next()
+        .await
+        .map_err(actix_web::error::ErrorBadRequest)?
+    {
+        if field.name() != "snapshot" {
+            continue;
+        }
+
+        let file_name = field
+            .content_disposition()
+            .get_filename()
+            .map(|name| name.to_string());
+
+        let mut file = NamedTempFile::new().map_err(actix_web::error::ErrorInternalServerError)?;
+        write_limited_stream(
+            &mut file,
+            field.map_err(actix_web::error::ErrorBadRequest),
+            max_size,
+        )
+        .await?;
 
-    snapshot.file.persist(&path)?;
+        return Ok(StreamedSnapshotUpload { file, file_name });
+    }
 
-    let absolute_path = path.canonicalize()?;
+    Err(actix_web::error::ErrorBadRequest(
+        "missing `snapshot` multipart field",
+    ))
+}
 
-    let snapshot_location = Url::from_file_path(&absolute_path).map_err(|_| {
-        StorageError::service_error(format!(
-            "Failed to convert path to URL: {}",
-            absolute_path.display()
-        ))
-    })?;
+/// Rejects a request with `408 Request Timeout` once it has been in flight
+/// for longer than `timeout`, so a stalled or hostile snapshot upload can't
+/// tie up a worker indefinitely.
+pub struct Deadline {
+    timeout: Duration,
+}
 
-    Ok(snapshot_location)
+impl Deadline {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Deadline
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = DeadlineMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<std::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(DeadlineMiddleware {
+            service,
+            timeout: self.timeout,
+        }))
+    }
+}
+
+pub struct DeadlineMiddleware<S> {
+    service: S,
+    timeout: Duration,
+}
+
+impl<S, B> Service<ServiceRequest> for DeadlineMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, std::result::Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let timeout = self.timeout;
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            match actix_web::rt::time::timeout(timeout, fut).await {
+                Ok(result) => result,
+                Err(_) => Err(actix_web::error::ErrorRequestTimeout(
+                    "snapshot upload exceeded the processing deadline",
+                )),
+            }
+        })
+    }
 }
 
 // Actix specific code
 pub async fn do_get_snapshot(
     dispatcher: &Dispatcher,
+    req: HttpRequest,
     collection_name: &str,
     snapshot_name: &str,
     wait: bool,
-) -> Result<NamedFile> {
-    let dispatcher = dispatcher.clone();
-    let collection_name = collection_name.to_string();
-    let snapshot_name = snapshot_name.to_string();
+) -> Result<HttpResponse> {
+    let spawned_dispatcher = dispatcher.clone();
+    let spawned_collection_name = collection_name.to_string();
+    let spawned_snapshot_name = snapshot_name.to_string();
 
     let task = tokio::spawn(async move {
-        let collection = dispatcher
-            .get_collection(&collection_name)
+        let collection = spawned_dispatcher
+            .get_collection(&spawned_collection_name)
             .await
             .map_err(storage_into_actix_error)
             .unwrap();
 
         collection
-            .get_snapshot_path(&snapshot_name)
+            .get_snapshot_path(&spawned_snapshot_name)
             .await
             .map_err(collection_into_actix_error)
             .unwrap()
     });
 
     if wait {
-        let result = task.await.unwrap();
-        Ok(NamedFile::open(result)?)
+        let local_path = task.await.unwrap();
+        serve_snapshot_file(&req, dispatcher.toc(), Some(collection_name), &local_path).await
     } else {
-        Ok(NamedFile::open("not_found")?)
+        Ok(NamedFile::open("not_found")?.into_response(&req))
     }
 }
 
@@ -140,59 +964,142 @@ async fn list_snapshots(
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
 
-    let response = do_list_snapshots(dispatcher.get_ref(), &collection_name, wait).await;
+    let store = snapshot_store(dispatcher.get_ref().toc());
+    if store.is_local() {
+        let response = do_list_snapshots(dispatcher.get_ref(), &collection_name, wait).await;
+        return process_response(response, timing);
+    }
+
+    let response = list_store_snapshots(store.as_ref(), Some(&collection_name)).await;
     process_response(response, timing)
 }
 
 #[post("/collections/{name}/snapshots")]
 async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
+    jobs: web::Data<SnapshotJobManager>,
     path: web::Path<String>,
     params: Query<SnapshottingParam>,
 ) -> impl Responder {
     let collection_name = path.into_inner();
     let wait = params.wait.unwrap_or(true);
-
     let timing = Instant::now();
-    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, wait).await;
-    process_response(response, timing)
+
+    if wait {
+        let response = create_collection_snapshot(dispatcher.get_ref(), &collection_name).await;
+        return process_response(response, timing);
+    }
+
+    let dispatcher = dispatcher.into_inner();
+    let job_id = jobs.enqueue(async move {
+        create_collection_snapshot(&dispatcher, &collection_name)
+            .await
+            .map(|description| description.name)
+    });
+    process_response(Ok(SnapshotJobAccepted { job_id }), timing)
+}
+
+/// Creates a collection snapshot and, if a non-local store is configured,
+/// syncs it out of local disk so `get_snapshot`/`list_snapshots` can find it
+/// there too.
+async fn create_collection_snapshot(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+) -> std::result::Result<SnapshotDescription, StorageError> {
+    let description = do_create_snapshot(dispatcher, collection_name, true).await?;
+
+    let local_path = dispatcher
+        .get_collection(collection_name)
+        .await
+        .map_err(|err| StorageError::service_error(err.to_string()))?
+        .get_snapshot_path(&description.name)
+        .await
+        .map_err(|err| StorageError::service_error(err.to_string()))?;
+    sync_created_snapshot(dispatcher.toc(), Some(collection_name), &local_path).await?;
+
+    Ok(description)
+}
+
+/// Enqueues a recovery job and returns its id, used by both the JSON and the
+/// multipart-upload recover endpoints since they only differ in how they
+/// obtain the `SnapshotRecover` location. On success the job reports the
+/// file name of the snapshot it recovered from.
+fn spawn_recover_job(
+    jobs: &SnapshotJobManager,
+    dispatcher: web::Data<Dispatcher>,
+    collection_name: String,
+    snapshot_recover: SnapshotRecover,
+) -> SnapshotJobId {
+    let dispatcher = dispatcher.into_inner();
+    let snapshot_name = snapshot_recover
+        .location
+        .path_segments()
+        .and_then(|segments| segments.last())
+        .unwrap_or_default()
+        .to_string();
+    jobs.enqueue(async move {
+        do_recover_from_snapshot(&dispatcher, &collection_name, snapshot_recover, true)
+            .await
+            .map(|_| snapshot_name)
+    })
 }
 
-#[post("/collections/{name}/snapshots/upload")]
 async fn upload_snapshot(
     dispatcher: web::Data<Dispatcher>,
+    jobs: web::Data<SnapshotJobManager>,
     collection: Path<CollectionPath>,
-    MultipartForm(form): MultipartForm<SnapshottingForm>,
+    multipart: Multipart,
     params: Query<SnapshotUploadingParam>,
 ) -> impl Responder {
     let timing = Instant::now();
-    let snapshot = form.snapshot;
     let wait = params.wait.unwrap_or(true);
 
-    let snapshot_location =
-        match do_save_uploaded_snapshot(dispatcher.get_ref(), &collection.name, snapshot) {
-            Ok(location) => location,
-            Err(err) => return process_response(Err(err), timing),
-        };
+    let upload = match receive_uploaded_snapshot(multipart, max_snapshot_upload_size()).await {
+        Ok(upload) => upload,
+        Err(err) => return err.error_response(),
+    };
+
+    let snapshot_location = match do_save_uploaded_snapshot(
+        dispatcher.get_ref(),
+        &collection.name,
+        upload.file,
+        upload.file_name,
+    )
+    .await
+    {
+        Ok(location) => location,
+        Err(err) => return process_response(Err(err), timing),
+    };
 
     let snapshot_recover = SnapshotRecover {
         location: snapshot_location,
         priority: params.priority,
     };
 
-    let response = do_recover_from_snapshot(
-        dispatcher.get_ref(),
-        &collection.name,
+    if wait {
+        let response = do_recover_from_snapshot(
+            dispatcher.get_ref(),
+            &collection.name,
+            snapshot_recover,
+            true,
+        )
+        .await;
+        return process_response(response, timing);
+    }
+
+    let job_id = spawn_recover_job(
+        &jobs,
+        dispatcher,
+        collection.into_inner().name,
         snapshot_recover,
-        wait,
-    )
-    .await;
-    process_response(response, timing)
+    );
+    process_response(Ok(SnapshotJobAccepted { job_id }), timing)
 }
 
 #[put("/collections/{name}/snapshots/recover")]
 async fn recover_from_snapshot(
     dispatcher: web::Data<Dispatcher>,
+    jobs: web::Data<SnapshotJobManager>,
     collection: Path<CollectionPath>,
     request: Json<SnapshotRecover>,
     params: Query<SnapshottingParam>,
@@ -201,25 +1108,55 @@ async fn recover_from_snapshot(
     let snapshot_recover = request.into_inner();
     let wait = params.wait.unwrap_or(true);
 
-    let response = do_recover_from_snapshot(
-        dispatcher.get_ref(),
-        &collection.name,
+    if wait {
+        let response = do_recover_from_snapshot(
+            dispatcher.get_ref(),
+            &collection.name,
+            snapshot_recover,
+            true,
+        )
+        .await;
+        return process_response(response, timing);
+    }
+
+    let job_id = spawn_recover_job(
+        &jobs,
+        dispatcher,
+        collection.into_inner().name,
         snapshot_recover,
-        wait,
-    )
-    .await;
-    process_response(response, timing)
+    );
+    process_response(Ok(SnapshotJobAccepted { job_id }), timing)
+}
+
+#[get("/collections/{name}/snapshots/jobs/{job_id}")]
+async fn get_snapshot_job_status(
+    jobs: web::Data<SnapshotJobManager>,
+    path: web::Path<(String, Uuid)>,
+) -> impl Responder {
+    let (_collection_name, job_id) = path.into_inner();
+    match jobs.status(job_id) {
+        Some(status) => HttpResponse::Ok().json(status),
+        None => HttpResponse::NotFound().finish(),
+    }
 }
 
 #[get("/collections/{name}/snapshots/{snapshot_name}")]
 async fn get_snapshot(
     dispatcher: web::Data<Dispatcher>,
+    req: HttpRequest,
     path: web::Path<(String, String)>,
     params: Query<SnapshottingParam>,
 ) -> impl Responder {
     let wait = params.wait.unwrap_or(true);
     let (collection_name, snapshot_name) = path.into_inner();
-    do_get_snapshot(dispatcher.get_ref(), &collection_name, &snapshot_name, wait).await
+    do_get_snapshot(
+        dispatcher.get_ref(),
+        req,
+        &collection_name,
+        &snapshot_name,
+        wait,
+    )
+    .await
 }
 
 #[get("/snapshots")]
@@ -229,30 +1166,73 @@ async fn list_full_snapshots(
 ) -> impl Responder {
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
-    let response = do_list_full_snapshots(dispatcher.get_ref(), wait).await;
+
+    let store = snapshot_store(dispatcher.get_ref().toc());
+    if store.is_local() {
+        let response = do_list_full_snapshots(dispatcher.get_ref(), wait).await;
+        return process_response(response, timing);
+    }
+
+    let response = list_store_snapshots(store.as_ref(), None).await;
     process_response(response, timing)
 }
 
 #[post("/snapshots")]
 async fn create_full_snapshot(
     dispatcher: web::Data<Dispatcher>,
+    jobs: web::Data<SnapshotJobManager>,
     params: Query<SnapshottingParam>,
 ) -> impl Responder {
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
-    let response = do_create_full_snapshot(dispatcher.get_ref(), wait).await;
-    process_response(response, timing)
+
+    if wait {
+        let response = create_full_snapshot_and_sync(dispatcher.get_ref()).await;
+        return process_response(response, timing);
+    }
+
+    let dispatcher = dispatcher.into_inner();
+    let job_id = jobs.enqueue(async move {
+        create_full_snapshot_and_sync(&dispatcher)
+            .await
+            .map(|description| description.name)
+    });
+    process_response(Ok(SnapshotJobAccepted { job_id }), timing)
+}
+
+/// Creates a full snapshot and, if a non-local store is configured, syncs it
+/// out of local disk so `get_full_snapshot`/`list_full_snapshots` can find it
+/// there too.
+async fn create_full_snapshot_and_sync(
+    dispatcher: &Dispatcher,
+) -> std::result::Result<SnapshotDescription, StorageError> {
+    let description = do_create_full_snapshot(dispatcher, true).await?;
+    let local_path = get_full_snapshot_path(dispatcher.toc(), &description.name).await?;
+    sync_created_snapshot(dispatcher.toc(), None, &local_path).await?;
+    Ok(description)
+}
+
+#[get("/snapshots/jobs/{job_id}")]
+async fn get_full_snapshot_job_status(
+    jobs: web::Data<SnapshotJobManager>,
+    path: web::Path<Uuid>,
+) -> impl Responder {
+    match jobs.status(path.into_inner()) {
+        Some(status) => HttpResponse::Ok().json(status),
+        None => HttpResponse::NotFound().finish(),
+    }
 }
 
 #[get("/snapshots/{snapshot_name}")]
 async fn get_full_snapshot(
     dispatcher: web::Data<Dispatcher>,
+    req: HttpRequest,
     path: web::Path<String>,
     params: Query<SnapshottingParam>,
 ) -> impl Responder {
     let snapshot_name = path.into_inner();
     let wait = params.wait.unwrap_or(true);
-    do_get_full_snapshot(dispatcher.get_ref(), &snapshot_name, wait).await
+    do_get_full_snapshot(dispatcher.get_ref(), req, &snapshot_name, wait).await
 }
 
 #[delete("/snapshots/{snapshot_name}")]
@@ -264,7 +1244,22 @@ async fn delete_full_snapshot(
     let snapshot_name = path.into_inner();
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
+
     let response = do_delete_full_snapshot(dispatcher.get_ref(), &snapshot_name, wait).await;
+    let response = match response {
+        Ok(deleted) => {
+            let store = snapshot_store(dispatcher.get_ref().toc());
+            match if store.is_local() {
+                Ok(())
+            } else {
+                store.delete(None, &snapshot_name).await
+            } {
+                Ok(()) => Ok(deleted),
+                Err(err) => Err(err),
+            }
+        }
+        Err(err) => Err(err),
+    };
     process_response(response, timing)
 }
 
@@ -277,22 +1272,78 @@ async fn delete_collection_snapshot(
     let (collection_name, snapshot_name) = path.into_inner();
     let timing = Instant::now();
     let wait = params.wait.unwrap_or(true);
+
     let response =
         do_delete_collection_snapshot(dispatcher.get_ref(), &collection_name, &snapshot_name, wait)
             .await;
+    let response = match response {
+        Ok(deleted) => {
+            let store = snapshot_store(dispatcher.get_ref().toc());
+            match if store.is_local() {
+                Ok(())
+            } else {
+                store.delete(Some(&collection_name), &snapshot_name).await
+            } {
+                Ok(()) => Ok(deleted),
+                Err(err) => Err(err),
+            }
+        }
+        Err(err) => Err(err),
+    };
     process_response(response, timing)
 }
 
 // Configure services
 pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
+    cfg.app_data(web::Data::new(SnapshotJobManager::default()));
     cfg.service(list_snapshots)
         .service(create_snapshot)
-        .service(upload_snapshot)
+        .service(
+            web::resource("/collections/{name}/snapshots/upload")
+                .wrap(Deadline::new(snapshot_upload_deadline()))
+                .route(web::post().to(upload_snapshot)),
+        )
         .service(recover_from_snapshot)
         .service(get_snapshot)
+        .service(get_snapshot_job_status)
         .service(list_full_snapshots)
         .service(create_full_snapshot)
         .service(get_full_snapshot)
+        .service(get_full_snapshot_job_status)
         .service(delete_full_snapshot)
-        .service(delete_collection_snapshot);
+        .service(delete_collection_snapshot)
+        .service(migrate_snapshots);
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn write_limited_stream_writes_all_chunks_within_limit() {
+        let mut file = NamedTempFile::new().unwrap();
+        let chunks = stream::iter(vec![
+            Ok(web::Bytes::from_static(b"hello ")),
+            Ok(web::Bytes::from_static(b"world")),
+        ]);
+
+        write_limited_stream(&mut file, chunks, 100).await.unwrap();
+
+        assert_eq!(std::fs::read(file.path()).unwrap(), b"hello world");
+    }
+
+    #[tokio::test]
+    async fn write_limited_stream_aborts_once_max_size_is_exceeded() {
+        let mut file = NamedTempFile::new().unwrap();
+        let chunks = stream::iter(vec![
+            Ok(web::Bytes::from_static(b"0123456789")),
+            Ok(web::Bytes::from_static(b"0123456789")),
+        ]);
+
+        let result = write_limited_stream(&mut file, chunks, 15).await;
+
+        assert!(result.is_err());
+    }
 }